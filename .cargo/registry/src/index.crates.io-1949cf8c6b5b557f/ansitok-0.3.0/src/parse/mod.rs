@@ -1,12 +0,0 @@
-mod ansi_parser;
-mod escape_sequence;
-mod output;
-mod parsers;
-mod sgr_parser;
-mod visual_attribute;
-
-pub use ansi_parser::{parse_ansi, AnsiIterator};
-pub use escape_sequence::EscapeCode;
-pub use output::Output;
-pub use sgr_parser::{parse_ansi_sgr, SGRParser};
-pub use visual_attribute::{AnsiColor, VisualAttribute};
@@ -0,0 +1,748 @@
+use ansi_str::{
+    ansi_downgrade, ansi_gradient, ansi_gradient_multi, ansi_wrap, get_blocks_with_mode, AnsiStr,
+    AnsiString, Color, ColorDepth, ColorLevel, GradientOptions, RenderMode,
+};
+use ansitok::{
+    parse_ansi, parse_ansi_sgr, parse_escape_code, scrub_ansi, AnsiColor, ElementKind, EscapeCode,
+    Output, VisualAttribute,
+};
+use nu_engine::CallExt;
+use nu_protocol::engine::{Call, Command, EngineState, Stack};
+use nu_protocol::{Category, PipelineData, Record, ShellError, Signature, Span, SyntaxShape, Type, Value};
+
+use crate::store::Store;
+
+#[derive(Clone)]
+pub struct AnsiCommand {
+    store: Store,
+}
+
+impl AnsiCommand {
+    pub fn new(store: Store) -> Self {
+        Self { store }
+    }
+}
+
+impl Command for AnsiCommand {
+    fn name(&self) -> &str {
+        ".ansi"
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::build(".ansi")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::String, Type::Any),
+            ])
+            .optional(
+                "id",
+                SyntaxShape::String,
+                "ID of the frame whose content to inspect (default: read text from pipeline)",
+            )
+            .switch(
+                "strip",
+                "emit plain text with all SGR escape sequences removed",
+                None,
+            )
+            .switch(
+                "scrub",
+                "emit plain text alongside the decoded style run for each span",
+                None,
+            )
+            .named(
+                "truncate",
+                SyntaxShape::Int,
+                "truncate to at most this many display columns, appending an ellipsis",
+                None,
+            )
+            .named(
+                "wrap",
+                SyntaxShape::Int,
+                "word-wrap to at most this many display columns, emitting a list of lines",
+                None,
+            )
+            .named(
+                "downgrade",
+                SyntaxShape::String,
+                "rewrite SGR colors to fit a terminal's color depth: ansi16, ansi256, or truecolor",
+                None,
+            )
+            .named(
+                "replace",
+                SyntaxShape::String,
+                "replace matches of this pattern, keeping the styling each match replaces (use with --with)",
+                None,
+            )
+            .named(
+                "with",
+                SyntaxShape::String,
+                "the replacement text for --replace",
+                None,
+            )
+            .named(
+                "split",
+                SyntaxShape::String,
+                "split on matches of this pattern, re-opening active styling in each piece",
+                None,
+            )
+            .switch(
+                "lines",
+                "split on newlines, re-opening active styling on each line",
+                None,
+            )
+            .switch(
+                "chars",
+                "emit one record per character with the fg/bg color active at that character",
+                None,
+            )
+            .named(
+                "matches",
+                SyntaxShape::String,
+                "list the stripped-text byte offset of each match of this pattern",
+                None,
+            )
+            .switch(
+                "trim",
+                "trim leading and trailing whitespace, preserving any styling around it",
+                None,
+            )
+            .named(
+                "gradient",
+                SyntaxShape::String,
+                "apply a color gradient across two or more #rrggbb stops, e.g. \"#ff0000,#00ff00,#0000ff\"",
+                None,
+            )
+            .named(
+                "cut",
+                SyntaxShape::String,
+                "extract the stripped-text byte range \"start:end\", keeping its styling",
+                None,
+            )
+            .switch(
+                "compact",
+                "with --cut, join each reopened style's SGR parameters into one escape sequence",
+                None,
+            )
+            .switch(
+                "blocks",
+                "split into styled runs and reconstruct each one's opening/closing escapes from its decoded Style",
+                None,
+            )
+            .switch(
+                "raw",
+                "with --blocks, replay each run's original SGR bytes verbatim instead of re-deriving them from its decoded Style",
+                None,
+            )
+            .switch(
+                "for-terminal",
+                "strip or downgrade colors to match stdout's detected color support",
+                None,
+            )
+            .switch(
+                "links",
+                "list OSC 8 hyperlinks and other OSC control strings embedded in the text",
+                None,
+            )
+            .switch(
+                "width",
+                "print the text's display width in columns, ignoring SGR escapes",
+                None,
+            )
+            .named(
+                "columns",
+                SyntaxShape::Int,
+                "keep only the first this-many display columns, keeping its styling",
+                None,
+            )
+            .category(Category::Experimental)
+    }
+
+    fn description(&self) -> &str {
+        "tokenize embedded ANSI/SGR escape sequences in frame content or piped text"
+    }
+
+    fn run(
+        &self,
+        engine_state: &EngineState,
+        stack: &mut Stack,
+        call: &Call,
+        input: PipelineData,
+    ) -> Result<PipelineData, ShellError> {
+        let span = call.head;
+        let strip = call.has_flag(engine_state, stack, "strip")?;
+        let scrub = call.has_flag(engine_state, stack, "scrub")?;
+        let truncate: Option<usize> = call.get_flag(engine_state, stack, "truncate")?;
+        let wrap: Option<usize> = call.get_flag(engine_state, stack, "wrap")?;
+        let downgrade: Option<String> = call.get_flag(engine_state, stack, "downgrade")?;
+        let replace: Option<String> = call.get_flag(engine_state, stack, "replace")?;
+        let with: Option<String> = call.get_flag(engine_state, stack, "with")?;
+        let split: Option<String> = call.get_flag(engine_state, stack, "split")?;
+        let lines = call.has_flag(engine_state, stack, "lines")?;
+        let chars = call.has_flag(engine_state, stack, "chars")?;
+        let matches: Option<String> = call.get_flag(engine_state, stack, "matches")?;
+        let trim = call.has_flag(engine_state, stack, "trim")?;
+        let gradient: Option<String> = call.get_flag(engine_state, stack, "gradient")?;
+        let cut: Option<String> = call.get_flag(engine_state, stack, "cut")?;
+        let compact = call.has_flag(engine_state, stack, "compact")?;
+        let blocks = call.has_flag(engine_state, stack, "blocks")?;
+        let raw = call.has_flag(engine_state, stack, "raw")?;
+        let for_terminal = call.has_flag(engine_state, stack, "for-terminal")?;
+        let links = call.has_flag(engine_state, stack, "links")?;
+        let width = call.has_flag(engine_state, stack, "width")?;
+        let columns: Option<i64> = call.get_flag(engine_state, stack, "columns")?;
+
+        let text = if let Some(id_str) = call.opt::<String>(engine_state, stack, 0)? {
+            let id = id_str.parse().map_err(|e| ShellError::TypeMismatch {
+                err_message: format!("Invalid ID format: {}", e),
+                span,
+            })?;
+
+            let frame = self.store.get(&id).ok_or_else(|| ShellError::GenericError {
+                error: "Frame not found".into(),
+                msg: format!("No frame found with ID: {}", id_str),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            })?;
+
+            let hash = frame.hash.ok_or_else(|| ShellError::GenericError {
+                error: "Frame has no content".into(),
+                msg: format!("Frame {} was not stored with content", id_str),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            })?;
+
+            let content = tokio::runtime::Runtime::new()
+                .map_err(|e| ShellError::GenericError {
+                    error: "Failed to create tokio runtime".into(),
+                    msg: e.to_string(),
+                    span: Some(span),
+                    help: None,
+                    inner: vec![],
+                })?
+                .block_on(self.store.cas_read(&hash))
+                .map_err(|e| ShellError::GenericError {
+                    error: "Failed to read content".into(),
+                    msg: e.to_string(),
+                    span: Some(span),
+                    help: None,
+                    inner: vec![],
+                })?;
+
+            String::from_utf8(content).map_err(|e| ShellError::GenericError {
+                error: "Content is not valid UTF-8".into(),
+                msg: e.to_string(),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            })?
+        } else {
+            match input.into_value(span)? {
+                Value::String { val, .. } => val,
+                _ => {
+                    return Err(ShellError::GenericError {
+                        error: "Missing input".into(),
+                        msg: "Provide a frame ID as an argument, or text via pipeline".into(),
+                        span: Some(span),
+                        help: None,
+                        inner: vec![],
+                    })
+                }
+            }
+        };
+
+        if strip {
+            Ok(PipelineData::Value(Value::string(strip_ansi(&text), span), None))
+        } else if scrub {
+            Ok(PipelineData::Value(scrub_to_value(&text, span), None))
+        } else if let Some(width) = truncate {
+            Ok(PipelineData::Value(
+                Value::string(text.ansi_truncate(width, "…").into_owned(), span),
+                None,
+            ))
+        } else if let Some(width) = wrap {
+            Ok(PipelineData::Value(
+                Value::list(
+                    ansi_wrap(&text, width)
+                        .into_iter()
+                        .map(|line| Value::string(line, span))
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if let Some(depth) = downgrade {
+            let depth = parse_color_depth(&depth, span)?;
+            Ok(PipelineData::Value(
+                Value::string(ansi_downgrade(&text, depth), span),
+                None,
+            ))
+        } else if let Some(from) = replace {
+            let to = with.ok_or_else(|| ShellError::GenericError {
+                error: "Missing --with".into(),
+                msg: "--replace requires --with to specify the replacement text".into(),
+                span: Some(span),
+                help: None,
+                inner: vec![],
+            })?;
+            Ok(PipelineData::Value(
+                Value::string(text.ansi_replace(&from, &to).into_owned(), span),
+                None,
+            ))
+        } else if let Some(pat) = split {
+            Ok(PipelineData::Value(
+                Value::list(
+                    text.ansi_split(pat.as_str())
+                        .map(|piece| Value::string(piece.into_owned(), span))
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if lines {
+            Ok(PipelineData::Value(
+                Value::list(
+                    text.ansi_lines()
+                        .map(|line| Value::string(line.into_owned(), span))
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if chars {
+            Ok(PipelineData::Value(
+                Value::list(
+                    text.ansi_char_indices()
+                        .map(|(_, c, style)| {
+                            let mut record = Record::new();
+                            record.push("char", Value::string(c.to_string(), span));
+                            record.push(
+                                "fg",
+                                style
+                                    .foreground()
+                                    .map(|color| Value::string(format!("{:?}", color), span))
+                                    .unwrap_or(Value::nothing(span)),
+                            );
+                            record.push(
+                                "bg",
+                                style
+                                    .background()
+                                    .map(|color| Value::string(format!("{:?}", color), span))
+                                    .unwrap_or(Value::nothing(span)),
+                            );
+                            Value::record(record, span)
+                        })
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if let Some(pat) = matches {
+            Ok(PipelineData::Value(
+                Value::list(
+                    text.ansi_match_indices(&pat)
+                        .map(|(start, matched)| {
+                            let mut record = Record::new();
+                            record.push("start", Value::int(start as i64, span));
+                            record.push("match", Value::string(matched.into_owned(), span));
+                            Value::record(record, span)
+                        })
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if trim {
+            Ok(PipelineData::Value(
+                Value::string(text.ansi_trim().into_owned(), span),
+                None,
+            ))
+        } else if let Some(stops) = gradient {
+            let stops = parse_gradient_stops(&stops, span)?;
+            let colored = match stops.as_slice() {
+                [start, end] => ansi_gradient(&text, *start, *end),
+                stops => ansi_gradient_multi(&text, stops, GradientOptions::default()),
+            };
+            Ok(PipelineData::Value(Value::string(colored, span), None))
+        } else if let Some(range) = cut {
+            let (start, end) = parse_cut_range(&range, span)?;
+            let cut = if compact {
+                text.ansi_cut_compact(start..end).into_owned()
+            } else {
+                AnsiString::new(&text).ansi_cut(start..end)
+            };
+            Ok(PipelineData::Value(Value::string(cut, span), None))
+        } else if blocks {
+            let mode = if raw { RenderMode::Raw } else { RenderMode::Canonical };
+            Ok(PipelineData::Value(
+                Value::list(
+                    get_blocks_with_mode(&text, mode)
+                        .map(|block| Value::string(block.to_string(), span))
+                        .collect(),
+                    span,
+                ),
+                None,
+            ))
+        } else if for_terminal {
+            let level = ColorLevel::auto(&std::io::stdout());
+            Ok(PipelineData::Value(
+                Value::string(text.render_for(level).into_owned(), span),
+                None,
+            ))
+        } else if links {
+            Ok(PipelineData::Value(
+                Value::list(ansi_links(&text, span), span),
+                None,
+            ))
+        } else if width {
+            Ok(PipelineData::Value(
+                Value::int(text.ansi_width() as i64, span),
+                None,
+            ))
+        } else if let Some(n) = columns {
+            let n = usize::try_from(n).map_err(|_| ShellError::TypeMismatch {
+                err_message: format!("Expected a non-negative column count, got: {}", n),
+                span,
+            })?;
+            Ok(PipelineData::Value(
+                Value::string(text.ansi_get_width(..n).unwrap_or_default().into_owned(), span),
+                None,
+            ))
+        } else {
+            Ok(PipelineData::Value(
+                Value::list(ansi_spans(&text, span), span),
+                None,
+            ))
+        }
+    }
+}
+
+fn parse_color_depth(name: &str, span: Span) -> Result<ColorDepth, ShellError> {
+    match name {
+        "ansi16" => Ok(ColorDepth::Ansi16),
+        "ansi256" => Ok(ColorDepth::Ansi256),
+        "truecolor" => Ok(ColorDepth::TrueColor),
+        other => Err(ShellError::GenericError {
+            error: "Invalid color depth".into(),
+            msg: format!(
+                "expected one of ansi16, ansi256, truecolor, got: {}",
+                other
+            ),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        }),
+    }
+}
+
+/// Parses a comma-separated list of `#rrggbb` colors into the stops for `--gradient`.
+fn parse_gradient_stops(stops: &str, span: Span) -> Result<Vec<Color>, ShellError> {
+    let stops = stops
+        .split(',')
+        .map(|s| parse_hex_color(s, span))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if stops.len() < 2 {
+        return Err(ShellError::GenericError {
+            error: "Invalid gradient stops".into(),
+            msg: "expected at least two #rrggbb stops, e.g. \"#ff0000,#0000ff\"".into(),
+            span: Some(span),
+            help: None,
+            inner: vec![],
+        });
+    }
+
+    Ok(stops)
+}
+
+fn parse_hex_color(hex: &str, span: Span) -> Result<Color, ShellError> {
+    let hex = hex.trim().strip_prefix('#').unwrap_or(hex.trim());
+    let invalid = || ShellError::GenericError {
+        error: "Invalid gradient color".into(),
+        msg: format!("expected a #rrggbb color, got: {}", hex),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    };
+
+    if hex.len() != 6 {
+        return Err(invalid());
+    }
+    let r = u8::from_str_radix(&hex[0..2], 16).map_err(|_| invalid())?;
+    let g = u8::from_str_radix(&hex[2..4], 16).map_err(|_| invalid())?;
+    let b = u8::from_str_radix(&hex[4..6], 16).map_err(|_| invalid())?;
+    Ok(Color::Rgb(r, g, b))
+}
+
+/// Parses a `"start:end"` byte range into a `(start, end)` pair for `--cut`.
+fn parse_cut_range(range: &str, span: Span) -> Result<(usize, usize), ShellError> {
+    let (start, end) = range.split_once(':').ok_or_else(|| ShellError::GenericError {
+        error: "Invalid cut range".into(),
+        msg: format!("expected \"start:end\", got: {}", range),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    })?;
+    let invalid = || ShellError::GenericError {
+        error: "Invalid cut range".into(),
+        msg: format!("expected \"start:end\" with integer bounds, got: {}", range),
+        span: Some(span),
+        help: None,
+        inner: vec![],
+    };
+    Ok((
+        start.parse().map_err(|_| invalid())?,
+        end.parse().map_err(|_| invalid())?,
+    ))
+}
+
+/// Strips SGR styling from `text` while keeping each styled run's decoded attributes,
+/// via `ansitok::scrub_ansi` - a separate decoding path from [`ansi_spans`] that already
+/// models SGR sub-resets (22-29, etc.) correctly since it tracks booleans per attribute
+/// rather than accumulating raw escape codes.
+fn scrub_to_value(text: &str, span: Span) -> Value {
+    let (cleaned, spans) = scrub_ansi(text);
+
+    let mut record = Record::new();
+    record.push("text", Value::string(cleaned, span));
+    record.push(
+        "styles",
+        Value::list(
+            spans
+                .into_iter()
+                .map(|s| {
+                    let mut rec = Record::new();
+                    rec.push("start", Value::int(s.range.start as i64, span));
+                    rec.push("end", Value::int(s.range.end as i64, span));
+                    rec.push("bold", Value::bool(s.style.bold, span));
+                    rec.push("faint", Value::bool(s.style.faint, span));
+                    rec.push("italic", Value::bool(s.style.italic, span));
+                    rec.push("underline", Value::bool(s.style.underline, span));
+                    rec.push("blink", Value::bool(s.style.blink, span));
+                    rec.push("reverse", Value::bool(s.style.reverse, span));
+                    rec.push("conceal", Value::bool(s.style.conceal, span));
+                    rec.push("strike", Value::bool(s.style.strike, span));
+                    Value::record(rec, span)
+                })
+                .collect(),
+            span,
+        ),
+    );
+    Value::record(record, span)
+}
+
+/// Drops every SGR escape sequence, keeping only the plain text.
+fn strip_ansi(text: &str) -> String {
+    parse_ansi(text)
+        .filter(|element| element.kind() == ElementKind::Text)
+        .map(|element| &text[element.range()])
+        .collect()
+}
+
+/// The attribute-kind "shapes" that a given sub-reset code (22, 23, 24, ...) turns off, as
+/// dummy instances to compare discriminants against. Values inside `Font`/`FgColor`/etc. are
+/// placeholders; only the variant matters.
+fn attributes_cleared_by_reset(n: u8) -> &'static [VisualAttribute] {
+    use VisualAttribute::*;
+    static FONT: [VisualAttribute; 1] = [Font(0)];
+    static BOLD_FAINT: [VisualAttribute; 2] = [Bold, Faint];
+    static ITALIC_FRAKTUR: [VisualAttribute; 2] = [Italic, Fraktur];
+    static UNDERLINE: [VisualAttribute; 2] = [Underline, DoubleUnderline];
+    static BLINK: [VisualAttribute; 2] = [SlowBlink, RapidBlink];
+    static INVERSE: [VisualAttribute; 1] = [Inverse];
+    static HIDE: [VisualAttribute; 1] = [Hide];
+    static CROSSEDOUT: [VisualAttribute; 1] = [Crossedout];
+    static FG_COLOR: [VisualAttribute; 1] = [FgColor(AnsiColor::Bit4(0))];
+    static BG_COLOR: [VisualAttribute; 1] = [BgColor(AnsiColor::Bit4(0))];
+    static PROPORTIONAL_SPACING: [VisualAttribute; 1] = [ProportionalSpacing];
+    static FRAMED_ENCIRCLED: [VisualAttribute; 2] = [Framed, Encircled];
+    static OVERLINED: [VisualAttribute; 1] = [Overlined];
+    static UNDR_COLOR: [VisualAttribute; 1] = [UndrColor(AnsiColor::Bit4(0))];
+    static IGRM: [VisualAttribute; 5] = [
+        IgrmUnderline,
+        IgrmDoubleUnderline,
+        IgrmOverline,
+        IgrmdDoubleOverline,
+        IgrmStressMarking,
+    ];
+    static SUPER_SUBSCRIPT: [VisualAttribute; 2] = [Superscript, Subscript];
+
+    match n {
+        10 => &FONT,
+        22 => &BOLD_FAINT,
+        23 => &ITALIC_FRAKTUR,
+        24 => &UNDERLINE,
+        25 => &BLINK,
+        27 => &INVERSE,
+        28 => &HIDE,
+        29 => &CROSSEDOUT,
+        39 => &FG_COLOR,
+        49 => &BG_COLOR,
+        50 => &PROPORTIONAL_SPACING,
+        54 => &FRAMED_ENCIRCLED,
+        55 => &OVERLINED,
+        59 => &UNDR_COLOR,
+        65 => &IGRM,
+        75 => &SUPER_SUBSCRIPT,
+        _ => &[],
+    }
+}
+
+/// Tokenizes `text` into a sequence of spans, each carrying the SGR attributes that were
+/// active when that span of text was emitted.
+///
+/// Attributes accumulate as SGR sequences are encountered: a later attribute of the same
+/// kind (e.g. a second `FgColor`) replaces the earlier one, `Reset(0)` clears everything, and
+/// a sub-reset (e.g. `Reset(22)` turning off only bold/faint) clears just the attribute kinds
+/// it's defined to turn off.
+fn ansi_spans(text: &str, span: Span) -> Vec<Value> {
+    let mut spans = Vec::new();
+    let mut active: Vec<VisualAttribute> = Vec::new();
+
+    for element in parse_ansi(text) {
+        match element.kind() {
+            ElementKind::Sgr => {
+                for output in parse_ansi_sgr(&text[element.range()]) {
+                    if let Output::Escape(attr) = output {
+                        match attr {
+                            VisualAttribute::Reset(0) => active.clear(),
+                            VisualAttribute::Reset(n) => {
+                                let cleared = attributes_cleared_by_reset(n);
+                                active.retain(|a| {
+                                    !cleared
+                                        .iter()
+                                        .any(|c| std::mem::discriminant(a) == std::mem::discriminant(c))
+                                });
+                            }
+                            _ => {
+                                active.retain(|a| {
+                                    std::mem::discriminant(a) != std::mem::discriminant(&attr)
+                                });
+                                active.push(attr);
+                            }
+                        }
+                    }
+                }
+            }
+            ElementKind::Text => {
+                let mut record = Record::new();
+                record.push("text", Value::string(&text[element.range()], span));
+                record.push(
+                    "attributes",
+                    Value::list(
+                        active.iter().map(|attr| attribute_to_value(attr, span)).collect(),
+                        span,
+                    ),
+                );
+                spans.push(Value::record(record, span));
+            }
+            _ => {}
+        }
+    }
+
+    spans
+}
+
+/// Decodes every OSC 8 hyperlink and other OSC control string (window title, clipboard,
+/// color palette, ...) in `text`, via [`ansitok::parse_escape_code`] run over each
+/// [`ElementKind::Osc`] element.
+fn ansi_links(text: &str, span: Span) -> Vec<Value> {
+    let mut links = Vec::new();
+
+    for element in parse_ansi(text) {
+        if element.kind() != ElementKind::Osc {
+            continue;
+        }
+
+        let Some((code, _)) = parse_escape_code(&text[element.range()]) else {
+            continue;
+        };
+
+        let mut record = Record::new();
+        match code {
+            EscapeCode::Hyperlink { params, uri } => {
+                record.push("kind", Value::string("hyperlink", span));
+                record.push("params", Value::string(params, span));
+                record.push("uri", Value::string(uri, span));
+            }
+            EscapeCode::OperatingSystemCommand { code, payload } => {
+                record.push("kind", Value::string("osc", span));
+                record.push("code", Value::int(code as i64, span));
+                record.push("payload", Value::string(payload, span));
+            }
+            _ => continue,
+        }
+        links.push(Value::record(record, span));
+    }
+
+    links
+}
+
+fn color_to_value(color: &AnsiColor, span: Span) -> Value {
+    let mut record = Record::new();
+    match color {
+        AnsiColor::Bit4(n) => {
+            record.push("kind", Value::string("bit4", span));
+            record.push("value", Value::int(*n as i64, span));
+        }
+        AnsiColor::Bit8(n) => {
+            record.push("kind", Value::string("bit8", span));
+            record.push("value", Value::int(*n as i64, span));
+        }
+        AnsiColor::Bit24 { r, g, b } => {
+            record.push("kind", Value::string("bit24", span));
+            record.push("r", Value::int(*r as i64, span));
+            record.push("g", Value::int(*g as i64, span));
+            record.push("b", Value::int(*b as i64, span));
+        }
+    }
+    Value::record(record, span)
+}
+
+fn attribute_to_value(attr: &VisualAttribute, span: Span) -> Value {
+    use VisualAttribute::*;
+
+    let name = match attr {
+        Bold => "bold",
+        Faint => "faint",
+        Italic => "italic",
+        Underline => "underline",
+        SlowBlink => "slow_blink",
+        RapidBlink => "rapid_blink",
+        Inverse => "inverse",
+        Hide => "hide",
+        Crossedout => "crossed_out",
+        Font(_) => "font",
+        Fraktur => "fraktur",
+        DoubleUnderline => "double_underline",
+        ProportionalSpacing => "proportional_spacing",
+        FgColor(_) => "fg_color",
+        BgColor(_) => "bg_color",
+        UndrColor(_) => "underline_color",
+        Framed => "framed",
+        Encircled => "encircled",
+        Overlined => "overlined",
+        IgrmUnderline => "ideogram_underline",
+        IgrmDoubleUnderline => "ideogram_double_underline",
+        IgrmOverline => "ideogram_overline",
+        IgrmdDoubleOverline => "ideogram_double_overline",
+        IgrmStressMarking => "ideogram_stress_marking",
+        Superscript => "superscript",
+        Subscript => "subscript",
+        Reset(_) => "reset",
+    };
+
+    let mut record = Record::new();
+    record.push("attribute", Value::string(name, span));
+
+    match attr {
+        Font(n) | Reset(n) => record.push("value", Value::int(*n as i64, span)),
+        FgColor(color) | BgColor(color) | UndrColor(color) => {
+            record.push("color", color_to_value(color, span))
+        }
+        _ => {}
+    }
+
+    Value::record(record, span)
+}
@@ -1,6 +1,6 @@
 use nu_engine::CallExt;
 use nu_protocol::engine::{Call, Command, EngineState, Stack};
-use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Type};
+use nu_protocol::{Category, PipelineData, ShellError, Signature, SyntaxShape, Type, Value};
 
 use crate::nu::util;
 use crate::store::Store;
@@ -23,8 +23,20 @@ impl Command for GetCommand {
 
     fn signature(&self) -> Signature {
         Signature::build(".get")
-            .input_output_types(vec![(Type::Nothing, Type::Any)])
-            .required("id", SyntaxShape::String, "The ID of the frame to retrieve")
+            .input_output_types(vec![
+                (Type::Nothing, Type::Any),
+                (Type::List(Box::new(Type::String)), Type::table()),
+            ])
+            .optional(
+                "id",
+                SyntaxShape::String,
+                "The ID of the frame to retrieve",
+            )
+            .switch(
+                "ignore-missing",
+                "yield null for IDs that aren't found instead of erroring",
+                None,
+            )
             .category(Category::Experimental)
     }
 
@@ -37,29 +49,70 @@ impl Command for GetCommand {
         engine_state: &EngineState,
         stack: &mut Stack,
         call: &Call,
-        _input: PipelineData,
+        input: PipelineData,
     ) -> Result<PipelineData, ShellError> {
-        let id_str: String = call.req(engine_state, stack, 0)?;
-        let id = id_str.parse().map_err(|e| ShellError::TypeMismatch {
-            err_message: format!("Invalid ID format: {}", e),
-            span: call.span(),
-        })?;
-
+        let span = call.head;
+        let ignore_missing = call.has_flag(engine_state, stack, "ignore-missing")?;
         let store = self.store.clone();
 
-        if let Some(frame) = store.get(&id) {
-            Ok(PipelineData::Value(
-                util::frame_to_value(&frame, call.head),
-                None,
-            ))
-        } else {
-            Err(ShellError::GenericError {
-                error: "Frame not found".into(),
-                msg: format!("No frame found with ID: {}", id_str),
-                span: Some(call.head),
-                help: None,
-                inner: vec![],
-            })
+        let fetch = |id_str: &str| -> Result<Option<Value>, ShellError> {
+            let id = id_str.parse().map_err(|e| ShellError::TypeMismatch {
+                err_message: format!("Invalid ID format: {}", e),
+                span,
+            })?;
+
+            match store.get(&id) {
+                Some(frame) => Ok(Some(util::frame_to_value(&frame, span))),
+                None if ignore_missing => Ok(None),
+                None => Err(ShellError::GenericError {
+                    error: "Frame not found".into(),
+                    msg: format!("No frame found with ID: {}", id_str),
+                    span: Some(span),
+                    help: None,
+                    inner: vec![],
+                }),
+            }
+        };
+
+        if let Some(id_str) = call.opt::<String>(engine_state, stack, 0)? {
+            return match fetch(&id_str)? {
+                Some(value) => Ok(PipelineData::Value(value, None)),
+                None => Ok(PipelineData::Value(Value::nothing(span), None)),
+            };
         }
+
+        let ids = match input.into_value(span)? {
+            Value::List { vals, .. } => vals,
+            _ => {
+                return Err(ShellError::GenericError {
+                    error: "Missing input".into(),
+                    msg: "Provide an ID as an argument, or a list of IDs via pipeline".into(),
+                    span: Some(span),
+                    help: None,
+                    inner: vec![],
+                })
+            }
+        };
+
+        let mut frames = Vec::with_capacity(ids.len());
+        for id in ids {
+            let id_str = match id {
+                Value::String { val, .. } => val,
+                other => {
+                    return Err(ShellError::TypeMismatch {
+                        err_message: format!("Expected string ID, got {}", other.get_type()),
+                        span,
+                    })
+                }
+            };
+
+            if let Some(value) = fetch(&id_str)? {
+                frames.push(value);
+            } else {
+                frames.push(Value::nothing(span));
+            }
+        }
+
+        Ok(PipelineData::Value(Value::list(frames, span), None))
     }
 }
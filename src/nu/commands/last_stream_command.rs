@@ -75,24 +75,27 @@ impl Command for LastStreamCommand {
         };
 
         if !follow {
-            // Non-follow mode: use sync path
+            // Non-follow mode: use sync path. Peek the first two frames so we can keep the
+            // empty/scalar special cases without collecting the whole read into a `Vec` -
+            // the remaining frames are streamed lazily into a `ListStream`.
             let options = ReadOptions::builder().last(n).maybe_topic(topic).build();
 
-            let frames: Vec<Value> = self
+            let mut frames = self
                 .store
                 .read_sync(options)
-                .map(|frame| util::frame_to_value(&frame, span, with_timestamp))
-                .collect();
-
-            return if frames.is_empty() {
-                Ok(PipelineData::Empty)
-            } else if frames.len() == 1 {
-                Ok(PipelineData::Value(
-                    frames.into_iter().next().unwrap(),
-                    None,
-                ))
-            } else {
-                Ok(PipelineData::Value(Value::list(frames, span), None))
+                .map(move |frame| util::frame_to_value(&frame, span, with_timestamp));
+
+            return match (frames.next(), frames.next()) {
+                (None, _) => Ok(PipelineData::Empty),
+                (Some(first), None) => Ok(PipelineData::Value(first, None)),
+                (Some(first), Some(second)) => {
+                    let stream = ListStream::new(
+                        std::iter::once(first).chain(std::iter::once(second)).chain(frames),
+                        span,
+                        Signals::empty(),
+                    );
+                    Ok(PipelineData::ListStream(stream, None))
+                }
             };
         }
 
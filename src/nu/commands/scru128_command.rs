@@ -149,7 +149,7 @@ impl Command for Scru128Command {
             .optional(
                 "subcommand",
                 SyntaxShape::String,
-                "subcommand: 'unpack' or 'pack'",
+                "subcommand: 'unpack', 'pack', 'min-id' or 'max-id'",
             )
             .optional(
                 "input",
@@ -196,11 +196,27 @@ impl Command for Scru128Command {
 
                 Ok(PipelineData::Value(Value::string(result, span), None))
             }
+            Some("min-id") => {
+                let ts = get_string_input(call, engine_state, stack, input, span)?;
+                let result = crate::scru128::min_id_for_rfc3339(&ts).map_err(|e| {
+                    scru128_error(format!("Failed to build min ID for timestamp: {e}"), span)
+                })?;
+
+                Ok(PipelineData::Value(Value::string(result, span), None))
+            }
+            Some("max-id") => {
+                let ts = get_string_input(call, engine_state, stack, input, span)?;
+                let result = crate::scru128::max_id_for_rfc3339(&ts).map_err(|e| {
+                    scru128_error(format!("Failed to build max ID for timestamp: {e}"), span)
+                })?;
+
+                Ok(PipelineData::Value(Value::string(result, span), None))
+            }
             Some(unknown) => Err(ShellError::GenericError {
                 error: "Invalid subcommand".into(),
                 msg: format!("Unknown subcommand: {unknown}"),
                 span: Some(span),
-                help: Some("Available subcommands: unpack, pack".into()),
+                help: Some("Available subcommands: unpack, pack, min-id, max-id".into()),
                 inner: vec![],
             }),
             None => {
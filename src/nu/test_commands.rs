@@ -289,6 +289,117 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_get_command_list() -> Result<(), Error> {
+        let (store, mut engine, ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::get_command::GetCommand::new(
+                store.clone(),
+            ))])
+            .unwrap();
+
+        let frame1 = store
+            .append(
+                Frame::builder("topic", ctx.id)
+                    .hash(store.cas_insert_sync("one")?)
+                    .build(),
+            )
+            .unwrap();
+        let frame2 = store
+            .append(
+                Frame::builder("topic", ctx.id)
+                    .hash(store.cas_insert_sync("two")?)
+                    .build(),
+            )
+            .unwrap();
+
+        let ids = format!(r#"["{}", "{}"]"#, frame1.id, frame2.id);
+        let retrieved = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            format!(".get {}", ids),
+        );
+
+        let list = retrieved.as_list().unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            list[0].get_data_by_key("id").unwrap().as_str().unwrap(),
+            frame1.id.to_string()
+        );
+        assert_eq!(
+            list[1].get_data_by_key("id").unwrap().as_str().unwrap(),
+            frame2.id.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_command_piped_list() -> Result<(), Error> {
+        let (store, mut engine, ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::get_command::GetCommand::new(
+                store.clone(),
+            ))])
+            .unwrap();
+
+        let frame = store
+            .append(
+                Frame::builder("topic", ctx.id)
+                    .hash(store.cas_insert_sync("test")?)
+                    .build(),
+            )
+            .unwrap();
+
+        let ids = format!(r#"["{}"]"#, frame.id);
+        let retrieved = nu_eval(&engine, PipelineData::empty(), format!("{} | .get", ids));
+
+        let list = retrieved.as_list().unwrap();
+        assert_eq!(list.len(), 1);
+        assert_eq!(
+            list[0].get_data_by_key("id").unwrap().as_str().unwrap(),
+            frame.id.to_string()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_command_ignore_missing() -> Result<(), Error> {
+        let (store, mut engine, ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::get_command::GetCommand::new(
+                store.clone(),
+            ))])
+            .unwrap();
+
+        let frame = store
+            .append(
+                Frame::builder("topic", ctx.id)
+                    .hash(store.cas_insert_sync("test")?)
+                    .build(),
+            )
+            .unwrap();
+        let missing_id = scru128::new();
+
+        let ids = format!(r#"["{}", "{}"]"#, frame.id, missing_id);
+        let retrieved = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            format!(".get {} --ignore-missing", ids),
+        );
+
+        let list = retrieved.as_list().unwrap();
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            list[0].get_data_by_key("id").unwrap().as_str().unwrap(),
+            frame.id.to_string()
+        );
+        assert!(list[1].is_nothing());
+
+        Ok(())
+    }
+
     #[test]
     fn test_scru128_generate() {
         let engine = setup_scru128_test_env();
@@ -405,4 +516,183 @@ mod tests {
         assert!(result.is_ok());
         assert!(result.unwrap().is_err());
     }
+
+    #[test]
+    fn test_scru128_min_id_max_id() {
+        let engine = setup_scru128_test_env();
+        let ts = "2024-01-01T00:00:00Z";
+
+        let min_id = nu_eval(&engine, PipelineData::empty(), format!(".id min-id {}", ts));
+        let max_id = nu_eval(&engine, PipelineData::empty(), format!(".id max-id {}", ts));
+
+        let min_id_str = min_id.as_str().unwrap();
+        let max_id_str = max_id.as_str().unwrap();
+
+        // Both are valid SCRU128 IDs for the same millisecond, so min-id sorts first and
+        // unpacking each recovers the same timestamp but opposite counter/node extremes.
+        assert!(scru128::Scru128Id::from_str(min_id_str).is_ok());
+        assert!(scru128::Scru128Id::from_str(max_id_str).is_ok());
+        assert!(min_id_str < max_id_str);
+
+        let min_unpacked = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            format!("\"{}\" | .id unpack", min_id_str),
+        );
+        let max_unpacked = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            format!("\"{}\" | .id unpack", max_id_str),
+        );
+
+        assert_eq!(
+            min_unpacked.as_record().unwrap().get("timestamp"),
+            max_unpacked.as_record().unwrap().get("timestamp"),
+        );
+        assert_eq!(
+            min_unpacked
+                .as_record()
+                .unwrap()
+                .get("counter_hi")
+                .unwrap()
+                .as_int()
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            max_unpacked
+                .as_record()
+                .unwrap()
+                .get("counter_hi")
+                .unwrap()
+                .as_int()
+                .unwrap(),
+            0xFF_FFFF
+        );
+    }
+
+    #[test]
+    fn test_ansi_command_strip() {
+        let (store, mut engine, _ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::ansi_command::AnsiCommand::new(
+                store,
+            ))])
+            .unwrap();
+
+        let stripped = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""\u{1b}[1mBold\u{1b}[0m plain" | .ansi --strip"#,
+        );
+        assert_eq!(stripped.as_str().unwrap(), "Bold plain");
+    }
+
+    #[test]
+    fn test_ansi_command_spans_clears_matching_attribute_on_sub_reset() {
+        let (store, mut engine, _ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::ansi_command::AnsiCommand::new(
+                store,
+            ))])
+            .unwrap();
+
+        // `\x1b[22m` resets only bold/faint (it's one of the SGR 22-29 sub-resets), so the
+        // second span must not still report `bold`.
+        let spans = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""\u{1b}[1mBold\u{1b}[22mPlain" | .ansi"#,
+        );
+        let spans = spans.as_list().unwrap();
+        assert_eq!(spans.len(), 2);
+
+        let first_attrs = spans[0].get_data_by_key("attributes").unwrap();
+        let first_attrs = first_attrs.as_list().unwrap();
+        assert_eq!(
+            first_attrs[0]
+                .get_data_by_key("attribute")
+                .unwrap()
+                .as_str()
+                .unwrap(),
+            "bold"
+        );
+
+        let second_attrs = spans[1].get_data_by_key("attributes").unwrap();
+        assert!(second_attrs.as_list().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_ansi_command_scrub() {
+        let (store, mut engine, _ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::ansi_command::AnsiCommand::new(
+                store,
+            ))])
+            .unwrap();
+
+        let scrubbed = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""\u{1b}[1mBold\u{1b}[22mPlain" | .ansi --scrub"#,
+        );
+        assert_eq!(
+            scrubbed.get_data_by_key("text").unwrap().as_str().unwrap(),
+            "BoldPlain"
+        );
+        let styles = scrubbed.get_data_by_key("styles").unwrap();
+        let styles = styles.as_list().unwrap();
+        assert_eq!(styles.len(), 1);
+        assert!(styles[0]
+            .get_data_by_key("bold")
+            .unwrap()
+            .as_bool()
+            .unwrap());
+    }
+
+    #[test]
+    fn test_ansi_command_truncate_and_wrap() {
+        let (store, mut engine, _ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::ansi_command::AnsiCommand::new(
+                store,
+            ))])
+            .unwrap();
+
+        let truncated = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""\u{1b}[31mhello world\u{1b}[39m" | .ansi --truncate 7"#,
+        );
+        assert!(truncated.as_str().unwrap().starts_with("\u{1b}[31mhello"));
+
+        let wrapped = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""foo bar baz" | .ansi --wrap 7"#,
+        );
+        let lines = wrapped.as_list().unwrap();
+        assert_eq!(
+            lines.iter().map(|l| l.as_str().unwrap()).collect::<Vec<_>>(),
+            vec!["foo bar", "baz"]
+        );
+    }
+
+    #[test]
+    fn test_ansi_command_downgrade() {
+        let (store, mut engine, _ctx) = setup_test_env();
+        engine
+            .add_commands(vec![Box::new(commands::ansi_command::AnsiCommand::new(
+                store,
+            ))])
+            .unwrap();
+
+        let downgraded = nu_eval(
+            &engine,
+            PipelineData::empty(),
+            r#""\u{1b}[38;2;255;0;0mred\u{1b}[39m" | .ansi --downgrade ansi16"#,
+        );
+        // truecolor downgraded to the 16-color palette no longer carries a `38;2;...` code.
+        assert!(!downgraded.as_str().unwrap().contains("38;2"));
+    }
 }
@@ -81,6 +81,36 @@ pub fn pack_from_json(
     Ok(scru_id.to_string())
 }
 
+/// Lowest SCRU128 ID that can be generated at `ms` (Unix epoch milliseconds): all trailing
+/// fields zeroed out. Pairs with [`max_id_for_timestamp`] to build a half-open `[min..max]`
+/// scan range for a time window, since SCRU128 IDs are lexicographically time-sortable.
+pub fn min_id_for_timestamp(ms: u64) -> Scru128Id {
+    Scru128Id::from_fields(ms, 0, 0, 0)
+}
+
+/// Highest SCRU128 ID that can be generated at `ms` (Unix epoch milliseconds): all trailing
+/// fields maxed out.
+pub fn max_id_for_timestamp(ms: u64) -> Scru128Id {
+    Scru128Id::from_fields(ms, 0xFF_FFFF, 0xFF_FFFF, 0xFFFF_FFFF)
+}
+
+fn parse_rfc3339_ms(ts: &str) -> Result<u64, Box<dyn std::error::Error + Send + Sync>> {
+    let datetime = chrono::DateTime::parse_from_rfc3339(ts)?;
+    Ok(datetime.timestamp_millis() as u64)
+}
+
+/// Same as [`min_id_for_timestamp`], but takes an RFC3339/ISO-8601 timestamp string, for use
+/// by the `.id` command and other CLI/JSON entry points that shouldn't have to reason about
+/// float-seconds or raw milliseconds.
+pub fn min_id_for_rfc3339(ts: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(min_id_for_timestamp(parse_rfc3339_ms(ts)?).to_string())
+}
+
+/// Same as [`max_id_for_timestamp`], but takes an RFC3339/ISO-8601 timestamp string.
+pub fn max_id_for_rfc3339(ts: &str) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    Ok(max_id_for_timestamp(parse_rfc3339_ms(ts)?).to_string())
+}
+
 pub fn pack() -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
     let mut buffer = String::new();
     io::stdin().read_to_string(&mut buffer)?;
@@ -13,6 +13,8 @@
 //! * Erase Line
 //! * Set Graphics mode
 //! * Set/Reset Text Mode
+//! * OSC 8 Hyperlinks
+//! * OSC control strings (window title, clipboard, color palette, ...)
 //!
 //! # Usage
 //!
@@ -48,11 +50,16 @@
 //! }
 //! ```
 
+#[cfg(not(any(feature = "std", test)))]
+extern crate alloc;
+
 mod element;
 mod parse;
+mod scrub;
 
 pub use element::{Element, ElementKind};
 pub use parse::{
-    parse_ansi, parse_ansi_sgr, AnsiColor, AnsiIterator, EscapeCode, Output, SGRParser,
-    VisualAttribute,
+    parse_ansi, parse_ansi_sgr, parse_escape_code, AnsiColor, AnsiIterator, Color, EscapeCode,
+    Output, SGRParser, SgrAttr, VisualAttribute,
 };
+pub use scrub::{scrub_ansi, Style, StyleSpan};
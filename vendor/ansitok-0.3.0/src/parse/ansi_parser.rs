@@ -146,7 +146,7 @@ impl Iterator for AnsiIterator<'_> {
         // we check here that it's the case and return it in such cases
         if self.text_length == 0 && esc_started {
             let start = self.start;
-            self.start = self.start + 1;
+            self.start += 1;
 
             self.text_length = self.pos - self.start;
 
@@ -165,7 +165,7 @@ impl vte::Perform for Performer {
             return;
         }
 
-        let is_sgr = c == 'm' && intermediates.first().is_none();
+        let is_sgr = c == 'm' && intermediates.is_empty();
         let element = if is_sgr {
             if params.is_empty() {
                 // Attr::Reset
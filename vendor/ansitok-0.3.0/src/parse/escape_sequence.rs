@@ -0,0 +1,585 @@
+use core::fmt::{self, Display, Formatter};
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::{string::String, vec::Vec};
+
+use super::parsers::parse_escape_sequence;
+
+/// An ANSI Escape Sequence.
+///
+/// You can find some specification on
+///
+/// - [wiki](https://en.wikipedia.org/wiki/ANSI_escape_code)
+/// - [VT51](https://web.archive.org/web/20090227051140/http://ascii-table.com/ansi-escape-sequences-vt-100.php)
+#[derive(Debug, Eq, PartialEq, PartialOrd, Ord, Clone, Hash)]
+#[non_exhaustive]
+pub enum EscapeCode<'a> {
+    /// A move cursor backward.
+    ///
+    /// Moves the cursor n (default 1) cells backwards.
+    CursorBackward(u32),
+    /// A cursor down.
+    ///
+    /// Moves the cursor n (default 1) cells down.
+    CursorDown(u32),
+    /// A move cursor forward.
+    ///
+    /// Moves the cursor n (default 1) cells forward.
+    CursorForward(u32),
+    /// A cursor position.
+    ///
+    /// The values are 1-based, and default to 1 (top left corner) if omitted.
+    CursorPos(u32, u32),
+    /// A restore of current cursor position/state.
+    CursorRestore,
+    /// A save of current cursor position/state.
+    CursorSave,
+    /// Set cursor key to application
+    CursorToApp,
+    /// A cursor up.
+    ///
+    /// Moves the cursor n (default 1) cells up.
+    CursorUp(u32),
+    /// Erase in Display.
+    EraseDisplay,
+    /// Erase in Display.
+    EraseLine,
+    /// A ESC sequence.
+    Escape,
+    /// Hide the cursor.
+    HideCursor,
+    /// An OSC 8 hyperlink, as in `ESC]8;params;uriBEL` (or `ST`-terminated).
+    ///
+    /// A closing link region is represented as empty `params` and `uri`.
+    Hyperlink {
+        /// The `key=value:...` parameter list, e.g. `id=xyz123`.
+        params: String,
+        /// The target URI.
+        uri: String,
+    },
+    /// A generic OSC (Operating System Command) control string, as in
+    /// `ESC]code;payloadBEL` (or `ST`-terminated).
+    ///
+    /// Covers e.g. OSC 0/1/2 (icon name / window title), OSC 52 (clipboard
+    /// set/query), and OSC 4/10/11 (color palette set/query).
+    OperatingSystemCommand {
+        /// The numeric OSC code, e.g. `0`, `52`, `11`.
+        code: u32,
+        /// The payload following the code, verbatim.
+        payload: String,
+    },
+    /// Reset auto repeat.
+    ResetAutoRepeat,
+    /// Reset auto wrap.
+    ResetAutoWrap,
+    /// Reset interlacin.
+    ResetInterlacing,
+    /// Erase in Display.
+    ResetMode(u8),
+    /// Select Graphic Rendition (SGR), sets display attributes.
+    SelectGraphicRendition(&'a str),
+    /// Set alternate keypad.
+    SetAlternateKeypad,
+    /// Set auto repeat.
+    SetAutoRepeat,
+    /// Set auto wrap.
+    SetAutoWrap,
+    /// Set number of columns to 132
+    SetCol132,
+    /// Set number of columns to 80
+    SetCol80,
+    /// Set cursor key to cursor.
+    SetCursorKeyToCursor,
+    /// Set G0 alt char ROM and spec. graphics.
+    SetG0AltAndSpecialGraph,
+    /// Set G0 alternate character ROM.
+    SetG0AlternateChar,
+    /// Set G0 special chars. & line set.
+    SetG0SpecialChars,
+    /// Set G1 alt char ROM and spec. graphics.
+    SetG1AltAndSpecialGraph,
+    /// Set G1 alternate character ROM.
+    SetG1AlternateChar,
+    /// Set G1 special chars. & line set.
+    SetG1SpecialChars,
+    /// Set interlacing.
+    SetInterlacing,
+    /// Set jump scrolling.
+    SetJumpScrolling,
+    /// Set line feed mode.
+    SetLineFeedMode,
+    /// Erase in Display.
+    SetMode(u8),
+    /// Set new line mode.
+    SetNewLineMode,
+    /// Set normal video.
+    SetNormalVideo,
+    /// Set numeric keypad.
+    SetNumericKeypad,
+    /// Set origin absolute.
+    SetOriginAbsolute,
+    /// Set origin relative.
+    SetOriginRelative,
+    /// Set reverse video.
+    SetReverseVideo,
+    /// Set single shift 2.
+    SetSingleShift2,
+    /// Set single shift 3.
+    SetSingleShift3,
+    /// Set smooth scroll.
+    SetSmoothScroll,
+    /// Set top and bottom lines of a window.
+    SetTopAndBottom(u32, u32),
+    /// Set United Kingdom G0 character set.
+    SetUKG0,
+    /// Set United Kingdom G1 character set.
+    SetUKG1,
+    /// Set United States G0 character set.
+    SetUSG0,
+    /// Set United States G1 character set.
+    SetUSG1,
+    /// Set VT52.
+    SetVT52,
+    /// Show the cursor.
+    ShowCursor,
+}
+
+impl EscapeCode<'_> {
+    /// Parse an escape code.
+    /// returns None if the sequence is not supported or it can't be parsed.
+    pub fn parse(text: &str) -> Option<EscapeCode<'_>> {
+        let (_, seq) = parse_escape_sequence(text).ok()?;
+        Some(seq)
+    }
+
+    /// Decode a [EscapeCode::SelectGraphicRendition] into a list of structured
+    /// [SgrAttr]s, returning `None` for any other variant.
+    ///
+    /// An empty parameter list is treated the same as `"0"` (reset), matching
+    /// how a bare `ESC[m` is interpreted by terminals. Unknown or malformed
+    /// codes are skipped rather than aborting the decode.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ansitok::{EscapeCode, SgrAttr, Color};
+    ///
+    /// let code = EscapeCode::SelectGraphicRendition("38;5;45;1");
+    /// assert_eq!(
+    ///     code.decode(),
+    ///     Some(vec![SgrAttr::Fg(Color::Indexed(45)), SgrAttr::Bold]),
+    /// );
+    /// ```
+    pub fn decode(&self) -> Option<Vec<SgrAttr>> {
+        match self {
+            EscapeCode::SelectGraphicRendition(params) => Some(decode_sgr(params)),
+            _ => None,
+        }
+    }
+}
+
+/// A color used by [SgrAttr::Fg] and [SgrAttr::Bg].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Color {
+    /// The standard black.
+    Black,
+    /// The standard red.
+    Red,
+    /// The standard green.
+    Green,
+    /// The standard yellow.
+    Yellow,
+    /// The standard blue.
+    Blue,
+    /// The standard magenta.
+    Magenta,
+    /// The standard cyan.
+    Cyan,
+    /// The standard white.
+    White,
+    /// The bright black.
+    BrightBlack,
+    /// The bright red.
+    BrightRed,
+    /// The bright green.
+    BrightGreen,
+    /// The bright yellow.
+    BrightYellow,
+    /// The bright blue.
+    BrightBlue,
+    /// The bright magenta.
+    BrightMagenta,
+    /// The bright cyan.
+    BrightCyan,
+    /// The bright white.
+    BrightWhite,
+    /// An indexed (256-color palette) color, as in `38;5;⟨n⟩`.
+    Indexed(u8),
+    /// A 24-bit truecolor color, as in `38;2;⟨r⟩;⟨g⟩;⟨b⟩`.
+    Rgb(u8, u8, u8),
+}
+
+impl Color {
+    fn base(code: u8) -> Self {
+        use Color::*;
+        match code {
+            0 => Black,
+            1 => Red,
+            2 => Green,
+            3 => Yellow,
+            4 => Blue,
+            5 => Magenta,
+            6 => Cyan,
+            _ => White,
+        }
+    }
+
+    fn bright(code: u8) -> Self {
+        use Color::*;
+        match code {
+            0 => BrightBlack,
+            1 => BrightRed,
+            2 => BrightGreen,
+            3 => BrightYellow,
+            4 => BrightBlue,
+            5 => BrightMagenta,
+            6 => BrightCyan,
+            _ => BrightWhite,
+        }
+    }
+}
+
+/// A single decoded SGR (Select Graphic Rendition) attribute, produced by
+/// [EscapeCode::decode].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SgrAttr {
+    /// Reset all attributes (`0`).
+    Reset,
+    /// A bold (`1`).
+    Bold,
+    /// A faint (`2`).
+    Faint,
+    /// An italic (`3`).
+    Italic,
+    /// An underline (`4`).
+    Underline,
+    /// A slow or rapid blink (`5`, `6`).
+    Blink,
+    /// A reverse video (`7`).
+    Reverse,
+    /// A conceal/hide (`8`).
+    Conceal,
+    /// A crossed-out/strike (`9`).
+    Strike,
+    /// A foreground color (`30..=38`, `90..=97`).
+    Fg(Color),
+    /// A background color (`40..=48`, `100..=107`).
+    Bg(Color),
+    /// Reset of bold/faint (`22`).
+    ResetBold,
+    /// Reset of italic (`23`).
+    ResetItalic,
+    /// Reset of underline (`24`).
+    ResetUnderline,
+    /// Reset of blink (`25`).
+    ResetBlink,
+    /// Reset of reverse video (`27`).
+    ResetReverse,
+    /// Reset of conceal (`28`).
+    ResetConceal,
+    /// Reset of strike (`29`).
+    ResetStrike,
+    /// Reset of the foreground color to default (`39`).
+    ResetFg,
+    /// Reset of the background color to default (`49`).
+    ResetBg,
+}
+
+fn decode_sgr(params: &str) -> Vec<SgrAttr> {
+    let codes: Vec<&str> = if params.is_empty() {
+        vec!["0"]
+    } else {
+        params.split(';').collect()
+    };
+
+    let mut attrs = Vec::new();
+    let mut i = 0;
+    while i < codes.len() {
+        let code: u8 = match codes[i].parse() {
+            Ok(code) => code,
+            Err(_) => {
+                i += 1;
+                continue;
+            }
+        };
+
+        match code {
+            0 => attrs.push(SgrAttr::Reset),
+            1 => attrs.push(SgrAttr::Bold),
+            2 => attrs.push(SgrAttr::Faint),
+            3 => attrs.push(SgrAttr::Italic),
+            4 => attrs.push(SgrAttr::Underline),
+            5 | 6 => attrs.push(SgrAttr::Blink),
+            7 => attrs.push(SgrAttr::Reverse),
+            8 => attrs.push(SgrAttr::Conceal),
+            9 => attrs.push(SgrAttr::Strike),
+            22 => attrs.push(SgrAttr::ResetBold),
+            23 => attrs.push(SgrAttr::ResetItalic),
+            24 => attrs.push(SgrAttr::ResetUnderline),
+            25 => attrs.push(SgrAttr::ResetBlink),
+            27 => attrs.push(SgrAttr::ResetReverse),
+            28 => attrs.push(SgrAttr::ResetConceal),
+            29 => attrs.push(SgrAttr::ResetStrike),
+            30..=37 => attrs.push(SgrAttr::Fg(Color::base(code - 30))),
+            38 => {
+                if let Some((color, consumed)) = decode_extended_color(&codes[i + 1..]) {
+                    attrs.push(SgrAttr::Fg(color));
+                    i += consumed;
+                }
+            }
+            39 => attrs.push(SgrAttr::ResetFg),
+            40..=47 => attrs.push(SgrAttr::Bg(Color::base(code - 40))),
+            48 => {
+                if let Some((color, consumed)) = decode_extended_color(&codes[i + 1..]) {
+                    attrs.push(SgrAttr::Bg(color));
+                    i += consumed;
+                }
+            }
+            49 => attrs.push(SgrAttr::ResetBg),
+            90..=97 => attrs.push(SgrAttr::Fg(Color::bright(code - 90))),
+            100..=107 => attrs.push(SgrAttr::Bg(Color::bright(code - 100))),
+            _ => {}
+        }
+
+        i += 1;
+    }
+
+    attrs
+}
+
+// Decodes the `5;⟨n⟩` or `2;⟨r⟩;⟨g⟩;⟨b⟩` tail of an extended `38`/`48` color
+// code, returning the color and the number of additional params consumed.
+fn decode_extended_color(rest: &[&str]) -> Option<(Color, usize)> {
+    match rest.first().copied() {
+        Some("5") => {
+            let index = rest.get(1)?.parse().ok()?;
+            Some((Color::Indexed(index), 2))
+        }
+        Some("2") => {
+            let r = rest.get(1)?.parse().ok()?;
+            let g = rest.get(2)?.parse().ok()?;
+            let b = rest.get(3)?.parse().ok()?;
+            Some((Color::Rgb(r, g, b), 4))
+        }
+        _ => None,
+    }
+}
+
+impl Display for EscapeCode<'_> {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        write!(formatter, "\u{1b}")?;
+
+        use EscapeCode::*;
+        match self {
+            Escape => write!(formatter, "\u{1b}"),
+            CursorPos(line, col) => write!(formatter, "[{};{}H", line, col),
+            CursorUp(amt) => write!(formatter, "[{}A", amt),
+            CursorDown(amt) => write!(formatter, "[{}B", amt),
+            CursorForward(amt) => write!(formatter, "[{}C", amt),
+            CursorBackward(amt) => write!(formatter, "[{}D", amt),
+            CursorSave => write!(formatter, "[s"),
+            CursorRestore => write!(formatter, "[u"),
+            EraseDisplay => write!(formatter, "[2J"),
+            EraseLine => write!(formatter, "[K"),
+            SelectGraphicRendition(mode) => write!(formatter, "[{}m", mode),
+            SetMode(mode) => write!(formatter, "[={}h", mode),
+            ResetMode(mode) => write!(formatter, "[={}l", mode),
+            ShowCursor => write!(formatter, "[?25h"),
+            HideCursor => write!(formatter, "[?25l"),
+            Hyperlink { params, uri } => write!(formatter, "]8;{};{}\u{7}", params, uri),
+            OperatingSystemCommand { code, payload } => {
+                write!(formatter, "]{};{}\u{7}", code, payload)
+            }
+            CursorToApp => write!(formatter, "[?1h"),
+            SetNewLineMode => write!(formatter, "[20h"),
+            SetCol132 => write!(formatter, "[?3h"),
+            SetSmoothScroll => write!(formatter, "[?4h"),
+            SetReverseVideo => write!(formatter, "[?5h"),
+            SetOriginRelative => write!(formatter, "[?6h"),
+            SetAutoWrap => write!(formatter, "[?7h"),
+            SetAutoRepeat => write!(formatter, "[?8h"),
+            SetInterlacing => write!(formatter, "[?9h"),
+            SetLineFeedMode => write!(formatter, "[20l"),
+            SetCursorKeyToCursor => write!(formatter, "[?1l"),
+            SetVT52 => write!(formatter, "[?2l"),
+            SetCol80 => write!(formatter, "[?3l"),
+            SetJumpScrolling => write!(formatter, "[?4l"),
+            SetNormalVideo => write!(formatter, "[?5l"),
+            SetOriginAbsolute => write!(formatter, "[?6l"),
+            ResetAutoWrap => write!(formatter, "[?7l"),
+            ResetAutoRepeat => write!(formatter, "[?8l"),
+            ResetInterlacing => write!(formatter, "[?9l"),
+            SetAlternateKeypad => write!(formatter, "="),
+            SetNumericKeypad => write!(formatter, ">"),
+            SetUKG0 => write!(formatter, "(A"),
+            SetUKG1 => write!(formatter, ")A"),
+            SetUSG0 => write!(formatter, "(B"),
+            SetUSG1 => write!(formatter, ")B"),
+            SetG0SpecialChars => write!(formatter, "(0"),
+            SetG1SpecialChars => write!(formatter, ")0"),
+            SetG0AlternateChar => write!(formatter, "(1"),
+            SetG1AlternateChar => write!(formatter, ")1"),
+            SetG0AltAndSpecialGraph => write!(formatter, "(2"),
+            SetG1AltAndSpecialGraph => write!(formatter, ")2"),
+            SetSingleShift2 => write!(formatter, "N"),
+            SetSingleShift3 => write!(formatter, "O"),
+            SetTopAndBottom(x, y) => write!(formatter, "[{};{}r", x, y),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fmt::Write;
+
+    #[test]
+    fn test_cursor_pos() {
+        let pos = EscapeCode::CursorPos(5, 20);
+
+        let mut buff = String::new();
+        write!(&mut buff, "{}", pos).expect("failed to write");
+
+        assert_eq!(buff, "\x1b[5;20H");
+    }
+
+    #[test]
+    fn test_decode_sgr_basic_attrs() {
+        let code = EscapeCode::SelectGraphicRendition("1;4;7");
+        assert_eq!(
+            code.decode(),
+            Some(vec![SgrAttr::Bold, SgrAttr::Underline, SgrAttr::Reverse])
+        );
+    }
+
+    #[test]
+    fn test_decode_sgr_empty_is_reset() {
+        let code = EscapeCode::SelectGraphicRendition("");
+        assert_eq!(code.decode(), Some(vec![SgrAttr::Reset]));
+    }
+
+    #[test]
+    fn test_decode_sgr_base_and_bright_colors() {
+        let code = EscapeCode::SelectGraphicRendition("31;102");
+        assert_eq!(
+            code.decode(),
+            Some(vec![
+                SgrAttr::Fg(Color::Red),
+                SgrAttr::Bg(Color::BrightGreen),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_sgr_indexed_color() {
+        let code = EscapeCode::SelectGraphicRendition("38;5;45");
+        assert_eq!(code.decode(), Some(vec![SgrAttr::Fg(Color::Indexed(45))]));
+    }
+
+    #[test]
+    fn test_decode_sgr_truecolor() {
+        let code = EscapeCode::SelectGraphicRendition("48;2;10;20;30");
+        assert_eq!(
+            code.decode(),
+            Some(vec![SgrAttr::Bg(Color::Rgb(10, 20, 30))])
+        );
+    }
+
+    #[test]
+    fn test_decode_sgr_skips_malformed_codes() {
+        let code = EscapeCode::SelectGraphicRendition("1;;38;5;9");
+        assert_eq!(
+            code.decode(),
+            Some(vec![SgrAttr::Bold, SgrAttr::Fg(Color::Indexed(9))])
+        );
+    }
+
+    #[test]
+    fn test_decode_non_sgr_returns_none() {
+        assert_eq!(EscapeCode::CursorUp(1).decode(), None);
+    }
+
+    // Round-trips every constructible variant through `to_string()` and back
+    // through `EscapeCode::parse`. Values are chosen so the serialization is
+    // not using any argument defaults, which would make the comparison
+    // trivially pass without exercising the parameter encoding.
+    #[test]
+    fn test_round_trip_every_variant() {
+        macro_rules! assert_round_trip {
+            ($code:expr) => {{
+                let text = $code.to_string();
+                assert_eq!(EscapeCode::parse(&text), Some($code));
+            }};
+        }
+
+        assert_round_trip!(EscapeCode::CursorBackward(3));
+        assert_round_trip!(EscapeCode::CursorDown(3));
+        assert_round_trip!(EscapeCode::CursorForward(3));
+        assert_round_trip!(EscapeCode::CursorPos(5, 20));
+        assert_round_trip!(EscapeCode::CursorRestore);
+        assert_round_trip!(EscapeCode::CursorSave);
+        assert_round_trip!(EscapeCode::CursorToApp);
+        assert_round_trip!(EscapeCode::CursorUp(3));
+        assert_round_trip!(EscapeCode::EraseDisplay);
+        assert_round_trip!(EscapeCode::EraseLine);
+        assert_round_trip!(EscapeCode::Escape);
+        assert_round_trip!(EscapeCode::HideCursor);
+        assert_round_trip!(EscapeCode::Hyperlink {
+            params: "id=1".to_string(),
+            uri: "https://example.com".to_string(),
+        });
+        assert_round_trip!(EscapeCode::OperatingSystemCommand {
+            code: 2,
+            payload: "window title".to_string(),
+        });
+        assert_round_trip!(EscapeCode::ResetAutoRepeat);
+        assert_round_trip!(EscapeCode::ResetAutoWrap);
+        assert_round_trip!(EscapeCode::ResetInterlacing);
+        assert_round_trip!(EscapeCode::ResetMode(7));
+        assert_round_trip!(EscapeCode::SelectGraphicRendition("1;31"));
+        assert_round_trip!(EscapeCode::SetAlternateKeypad);
+        assert_round_trip!(EscapeCode::SetAutoRepeat);
+        assert_round_trip!(EscapeCode::SetAutoWrap);
+        assert_round_trip!(EscapeCode::SetCol132);
+        assert_round_trip!(EscapeCode::SetCol80);
+        assert_round_trip!(EscapeCode::SetCursorKeyToCursor);
+        assert_round_trip!(EscapeCode::SetG0AltAndSpecialGraph);
+        assert_round_trip!(EscapeCode::SetG0AlternateChar);
+        assert_round_trip!(EscapeCode::SetG0SpecialChars);
+        assert_round_trip!(EscapeCode::SetG1AltAndSpecialGraph);
+        assert_round_trip!(EscapeCode::SetG1AlternateChar);
+        assert_round_trip!(EscapeCode::SetG1SpecialChars);
+        assert_round_trip!(EscapeCode::SetInterlacing);
+        assert_round_trip!(EscapeCode::SetJumpScrolling);
+        assert_round_trip!(EscapeCode::SetLineFeedMode);
+        assert_round_trip!(EscapeCode::SetMode(7));
+        assert_round_trip!(EscapeCode::SetNewLineMode);
+        assert_round_trip!(EscapeCode::SetNormalVideo);
+        assert_round_trip!(EscapeCode::SetNumericKeypad);
+        assert_round_trip!(EscapeCode::SetOriginAbsolute);
+        assert_round_trip!(EscapeCode::SetOriginRelative);
+        assert_round_trip!(EscapeCode::SetReverseVideo);
+        assert_round_trip!(EscapeCode::SetSingleShift2);
+        assert_round_trip!(EscapeCode::SetSingleShift3);
+        assert_round_trip!(EscapeCode::SetSmoothScroll);
+        assert_round_trip!(EscapeCode::SetTopAndBottom(1, 24));
+        assert_round_trip!(EscapeCode::SetUKG0);
+        assert_round_trip!(EscapeCode::SetUKG1);
+        assert_round_trip!(EscapeCode::SetUSG0);
+        assert_round_trip!(EscapeCode::SetUSG1);
+        assert_round_trip!(EscapeCode::SetVT52);
+        assert_round_trip!(EscapeCode::ShowCursor);
+    }
+}
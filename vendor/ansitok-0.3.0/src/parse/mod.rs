@@ -0,0 +1,21 @@
+mod ansi_parser;
+mod escape_sequence;
+mod output;
+mod parsers;
+mod sgr_parser;
+mod visual_attribute;
+
+pub use ansi_parser::{parse_ansi, AnsiIterator};
+pub use escape_sequence::{Color, EscapeCode, SgrAttr};
+pub use output::Output;
+pub use sgr_parser::{parse_ansi_sgr, SGRParser};
+pub use visual_attribute::{AnsiColor, VisualAttribute};
+
+/// Decodes a single escape sequence (starting with ESC) from the front of `input`,
+/// e.g. an `ElementKind::Csi` or `ElementKind::Osc` element's text, returning the
+/// decoded [`EscapeCode`] and how many bytes of `input` it consumed.
+pub fn parse_escape_code(input: &str) -> Option<(EscapeCode<'_>, usize)> {
+    parsers::parse_escape_sequence(input)
+        .ok()
+        .map(|(rest, code)| (code, input.len() - rest.len()))
+}
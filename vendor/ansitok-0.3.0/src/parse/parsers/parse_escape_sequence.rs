@@ -1,3 +1,6 @@
+#[cfg(not(any(feature = "std", test)))]
+use alloc::string::ToString;
+
 use nom::{
     branch::alt,
     bytes::{complete::take_until, streaming::tag},
@@ -7,7 +10,7 @@ use nom::{
 
 use crate::parse::escape_sequence::EscapeCode;
 
-use super::parse_util::{parse_u32_default, parse_u8};
+use super::parse_util::{parse_u32_default, parse_u32_required, parse_u8};
 
 pub(crate) fn parse_escape_sequence(input: &str) -> IResult<&str, EscapeCode<'_>> {
     let (input, _) = tag("\u{1b}")(input)?;
@@ -26,6 +29,7 @@ mod parse {
         alt((
             alt((
                 escape,
+                hyperlink,
                 cursor_pos,
                 cursor_up,
                 cursor_down,
@@ -79,6 +83,7 @@ mod parse {
                 set_g1_graph,
                 set_single_shift2,
                 set_single_shift3,
+                operating_system_command,
                 graphics_mode, // greedy so must be at the end
             )),
         ))(input)
@@ -187,13 +192,56 @@ mod parse {
         tag_parser!(set_single_shift2,    "N",      EscapeCode::SetSingleShift2);
         tag_parser!(set_single_shift3,    "O",      EscapeCode::SetSingleShift3);
 
-        pub fn graphics_mode(input: &str) -> IResult<&str, EscapeCode> {
+        pub fn graphics_mode(input: &str) -> IResult<&str, EscapeCode<'_>> {
             let (input, _) = tag("[")(input)?;
             let (input, mode) = take_until("m")(input)?;
             let (input, _) = tag("m")(input)?;
 
             Ok((input, EscapeCode::SelectGraphicRendition(mode)))
         }
+
+        pub fn hyperlink(input: &str) -> IResult<&str, EscapeCode<'_>> {
+            let (input, _) = tag("]8;")(input)?;
+            let (input, params) = take_until(";")(input)?;
+            let (input, _) = tag(";")(input)?;
+            let (input, uri) = take_until_osc_terminator(input)?;
+            let (input, _) = osc_terminator(input)?;
+
+            Ok((
+                input,
+                EscapeCode::Hyperlink {
+                    params: params.to_string(),
+                    uri: uri.to_string(),
+                },
+            ))
+        }
+
+        pub fn operating_system_command(input: &str) -> IResult<&str, EscapeCode<'_>> {
+            let (input, _) = tag("]")(input)?;
+            let (input, code) = parse_u32_required(input)?;
+            let (input, _) = tag(";")(input)?;
+            let (input, payload) = take_until_osc_terminator(input)?;
+            let (input, _) = osc_terminator(input)?;
+
+            Ok((
+                input,
+                EscapeCode::OperatingSystemCommand {
+                    code,
+                    payload: payload.to_string(),
+                },
+            ))
+        }
+
+        // An OSC string is terminated by either the BEL byte or the two-byte
+        // ST (`ESC \`) sequence. Shared by the OSC 8 hyperlink parser and, in
+        // the future, any other OSC-framed escape sequence.
+        pub(super) fn take_until_osc_terminator(input: &str) -> IResult<&str, &str> {
+            alt((take_until("\u{7}"), take_until("\u{1b}\\")))(input)
+        }
+
+        pub(super) fn osc_terminator(input: &str) -> IResult<&str, &str> {
+            alt((tag("\u{7}"), tag("\u{1b}\\")))(input)
+        }
     }
 }
 
@@ -299,4 +347,66 @@ mod tests {
 
     test_parse_default!(cursor_pos_default, "\u{1b}[H");
     test_parse_default!(cursor_up_default, "\u{1b}[A");
+
+    test_parse!(hyperlink, "\u{1b}]8;id=1;https://example.com\u{7}");
+    test_parse!(hyperlink_close, "\u{1b}]8;;\u{7}");
+
+    #[test]
+    fn test_hyperlink_st_terminated() {
+        let (rest, ret) =
+            parse_escape_sequence("\u{1b}]8;;https://example.com\u{1b}\\tail").unwrap();
+
+        assert_eq!(
+            ret,
+            EscapeCode::Hyperlink {
+                params: String::new(),
+                uri: "https://example.com".to_string(),
+            }
+        );
+        assert_eq!(rest, "tail");
+    }
+
+    #[test]
+    fn test_hyperlink_uri_with_csi_like_bytes() {
+        let (_, ret) =
+            parse_escape_sequence("\u{1b}]8;;file:///a;b\u{1b}[31m.rs\u{7}").unwrap();
+
+        assert_eq!(
+            ret,
+            EscapeCode::Hyperlink {
+                params: String::new(),
+                uri: "file:///a;b\u{1b}[31m.rs".to_string(),
+            }
+        );
+    }
+
+    test_parse!(set_window_title, "\u{1b}]2;my title\u{7}");
+    test_parse!(osc_clipboard_query, "\u{1b}]52;c;?\u{7}");
+
+    #[test]
+    fn test_osc_color_query_st_terminated() {
+        let (rest, ret) =
+            parse_escape_sequence("\u{1b}]11;?\u{1b}\\tail").unwrap();
+
+        assert_eq!(
+            ret,
+            EscapeCode::OperatingSystemCommand {
+                code: 11,
+                payload: "?".to_string(),
+            }
+        );
+        assert_eq!(rest, "tail");
+    }
+
+    #[test]
+    fn test_osc_does_not_swallow_hyperlinks() {
+        let (_, ret) = parse_escape_sequence("\u{1b}]8;;https://a.b\u{7}").unwrap();
+        assert_eq!(
+            ret,
+            EscapeCode::Hyperlink {
+                params: String::new(),
+                uri: "https://a.b".to_string(),
+            }
+        );
+    }
 }
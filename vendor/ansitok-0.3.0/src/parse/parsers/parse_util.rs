@@ -16,6 +16,10 @@ pub(crate) fn parse_u32_default(input: &str, default: u32) -> IResult<&str, u32>
     parse_u32(input).map(|(input, n)| (input, n.unwrap_or(default)))
 }
 
+pub(crate) fn parse_u32_required(input: &str) -> IResult<&str, u32> {
+    map_res(digit1, decimal_u32)(input)
+}
+
 pub(crate) fn parse_u32(input: &str) -> IResult<&str, Option<u32>> {
     opt(map_res(digit1, decimal_u32))(input)
 }
@@ -0,0 +1,188 @@
+use core::ops::Range;
+
+#[cfg(not(any(feature = "std", test)))]
+use alloc::{string::String, vec::Vec};
+
+use crate::{parse_ansi, Color, ElementKind, EscapeCode, SgrAttr};
+
+/// The active styling at a point in text, as decoded from [SgrAttr]s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Style {
+    /// A bold.
+    pub bold: bool,
+    /// A faint.
+    pub faint: bool,
+    /// An italic.
+    pub italic: bool,
+    /// An underline.
+    pub underline: bool,
+    /// A slow or rapid blink.
+    pub blink: bool,
+    /// A reverse video.
+    pub reverse: bool,
+    /// A conceal/hide.
+    pub conceal: bool,
+    /// A crossed-out/strike.
+    pub strike: bool,
+    /// A foreground color.
+    pub fg: Option<Color>,
+    /// A background color.
+    pub bg: Option<Color>,
+}
+
+impl Style {
+    fn apply(&mut self, attr: SgrAttr) {
+        use SgrAttr::*;
+        match attr {
+            Reset => *self = Style::default(),
+            Bold => self.bold = true,
+            Faint => self.faint = true,
+            Italic => self.italic = true,
+            Underline => self.underline = true,
+            Blink => self.blink = true,
+            Reverse => self.reverse = true,
+            Conceal => self.conceal = true,
+            Strike => self.strike = true,
+            Fg(color) => self.fg = Some(color),
+            Bg(color) => self.bg = Some(color),
+            ResetBold => self.bold = false,
+            ResetItalic => self.italic = false,
+            ResetUnderline => self.underline = false,
+            ResetBlink => self.blink = false,
+            ResetReverse => self.reverse = false,
+            ResetConceal => self.conceal = false,
+            ResetStrike => self.strike = false,
+            ResetFg => self.fg = None,
+            ResetBg => self.bg = None,
+        }
+    }
+}
+
+/// A run of cleaned-output text sharing the same [Style], produced by
+/// [scrub_ansi].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyleSpan {
+    /// The byte range in the cleaned output this style applies to.
+    pub range: Range<usize>,
+    /// The styling active over `range`.
+    pub style: Style,
+}
+
+/// Strips ANSI escape sequences from `input`, returning the cleaned text
+/// alongside the styling that was active over each styled run of it.
+///
+/// SGR sequences are decoded (via [EscapeCode::decode]) into the running
+/// [Style], resetting on `0`; cursor movement, erase, and mode sequences are
+/// simply dropped. Unstyled text is not given a [StyleSpan].
+///
+/// # Example
+///
+/// ```
+/// use ansitok::scrub_ansi;
+///
+/// let (cleaned, spans) = scrub_ansi("\x1b[31mred\x1b[0m plain");
+/// assert_eq!(cleaned, "red plain");
+/// assert_eq!(spans.len(), 1);
+/// assert_eq!(&cleaned[spans[0].range.clone()], "red");
+/// ```
+pub fn scrub_ansi(input: &str) -> (String, Vec<StyleSpan>) {
+    let mut cleaned = String::new();
+    let mut spans = Vec::new();
+    let mut style = Style::default();
+
+    for element in parse_ansi(input) {
+        let text = &input[element.range()];
+
+        match element.kind() {
+            ElementKind::Text => {
+                let start = cleaned.len();
+                cleaned.push_str(text);
+                let end = cleaned.len();
+
+                if style != Style::default() {
+                    spans.push(StyleSpan {
+                        range: start..end,
+                        style,
+                    });
+                }
+            }
+            ElementKind::Sgr => {
+                if let Some(attrs) = EscapeCode::parse(text).and_then(|code| code.decode()) {
+                    for attr in attrs {
+                        style.apply(attr);
+                    }
+                }
+            }
+            ElementKind::Csi | ElementKind::Osc | ElementKind::Esc => {}
+        }
+    }
+
+    (cleaned, spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrub_ansi_strips_escapes() {
+        let (cleaned, _) = scrub_ansi("\x1b[31mred\x1b[0m plain");
+        assert_eq!(cleaned, "red plain");
+    }
+
+    #[test]
+    fn test_scrub_ansi_spans_styled_runs() {
+        let (cleaned, spans) = scrub_ansi("\x1b[31mred\x1b[0m plain");
+        assert_eq!(
+            spans,
+            vec![StyleSpan {
+                range: 0..3,
+                style: Style {
+                    fg: Some(Color::Red),
+                    ..Style::default()
+                },
+            }]
+        );
+        assert_eq!(&cleaned[spans[0].range.clone()], "red");
+    }
+
+    #[test]
+    fn test_scrub_ansi_no_span_for_plain_text() {
+        let (cleaned, spans) = scrub_ansi("plain text");
+        assert_eq!(cleaned, "plain text");
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn test_scrub_ansi_drops_cursor_and_erase_sequences() {
+        let (cleaned, spans) = scrub_ansi("\x1b[2J\x1b[5Chello\x1b[K");
+        assert_eq!(cleaned, "hello");
+        assert!(spans.is_empty());
+    }
+
+    #[test]
+    fn test_scrub_ansi_multiple_styled_runs() {
+        let (cleaned, spans) = scrub_ansi("\x1b[1mA\x1b[0m\x1b[3mB\x1b[0m");
+        assert_eq!(cleaned, "AB");
+        assert_eq!(
+            spans,
+            vec![
+                StyleSpan {
+                    range: 0..1,
+                    style: Style {
+                        bold: true,
+                        ..Style::default()
+                    },
+                },
+                StyleSpan {
+                    range: 1..2,
+                    style: Style {
+                        italic: true,
+                        ..Style::default()
+                    },
+                },
+            ]
+        );
+    }
+}
@@ -70,10 +70,18 @@ pub mod tokenizer;
 ///
 /// - `NumConversionFailed(String)`: An error indicating that a number conversion failed during expansion.
 ///                                 It contains a string representing the value that failed to be converted.
+/// - `MixedRangeBounds(String, String)`: An error indicating that a range mixed an alphabetic bound
+///                                 with a numeric one (e.g. `{a..5}`).
 #[derive(Debug, PartialEq)]
 pub enum ExpansionError {
     /// Error indicating that a number conversion failed during expansion.
     NumConversionFailed(String),
+    /// Error indicating that a range's `from` and `to` bounds can't be reconciled, because one
+    /// is a single alphabetic character and the other isn't (e.g. `{a..5}` or `{ab..z}`).
+    MixedRangeBounds(String, String),
+    /// Error indicating that a range step was zero or failed to parse as a number, e.g.
+    /// `{0..10..0}` or `{0..10..x}`.
+    InvalidStep(String),
 }
 
 impl std::fmt::Display for ExpansionError {
@@ -82,6 +90,12 @@ impl std::fmt::Display for ExpansionError {
             ExpansionError::NumConversionFailed(content) => {
                 write!(f, "Number conversion of \"{}\" failed.", content)
             }
+            ExpansionError::MixedRangeBounds(from, to) => {
+                write!(f, "Range bounds \"{}\" and \"{}\" can't be mixed.", from, to)
+            }
+            ExpansionError::InvalidStep(content) => {
+                write!(f, "Range step \"{}\" must be a non-zero number.", content)
+            }
         }
     }
 }
@@ -123,8 +137,40 @@ impl std::error::Error for ExpansionError {}
 ///
 /// This function operates on valid parsed nodes and does not use unsafe code internally.
 pub fn expand(node: &crate::parser::Node) -> Result<Vec<String>, ExpansionError> {
+    expand_iter(node).collect()
+}
+
+/// Lazily expands the given parsed node, yielding each combination on demand instead of
+/// materializing the full result set upfront.
+///
+/// This avoids the memory blowup [`expand`] can hit on deeply nested patterns, where the
+/// cartesian product of prefix, inside and postfix combinations can get large before the
+/// caller has consumed any of it. Ordering matches [`expand`] exactly.
+///
+/// # Examples
+///
+/// ```
+/// use bracoxide::parser::Node;
+/// use bracoxide::expand_iter;
+///
+/// let node = Node::Text { message: "Hello".to_owned().into(), start: 0 };
+/// let expanded: Vec<_> = expand_iter(&node).collect();
+/// assert_eq!(expanded, vec![Ok("Hello".to_owned())]);
+/// ```
+pub fn expand_iter(node: &crate::parser::Node) -> impl Iterator<Item = Result<String, ExpansionError>> {
+    // `expand_node` consumes its `Node` by value, so it can walk into owned `Box<Node>`
+    // children without borrowing from `node` - which keeps the returned iterator's type
+    // (and lifetime) simple at the cost of one cheap clone of the (typically small) AST.
+    expand_node(node.clone())
+}
+
+fn expand_node(
+    node: parser::Node,
+) -> Box<dyn Iterator<Item = Result<String, ExpansionError>>> {
     match node {
-        parser::Node::Text { message, start: _ } => Ok(vec![message.as_ref().to_owned()]),
+        parser::Node::Text { message, start: _ } => {
+            Box::new(std::iter::once(Ok(message.as_ref().to_owned())))
+        }
         parser::Node::BraceExpansion {
             prefix,
             inside,
@@ -132,80 +178,177 @@ pub fn expand(node: &crate::parser::Node) -> Result<Vec<String>, ExpansionError>
             start: _,
             end: _,
         } => {
-            let mut inner = vec![];
-            let prefixs: Vec<String> = if let Some(prefix) = prefix {
-                expand(prefix)?
-            } else {
-                vec!["".to_owned()]
+            let branch = |node: Option<Box<parser::Node>>| -> Result<Vec<String>, ExpansionError> {
+                match node {
+                    Some(node) => expand_node(*node).collect(),
+                    None => Ok(vec!["".to_owned()]),
+                }
             };
-            let insides: Vec<String> = if let Some(inside) = inside {
-                expand(inside)?
-            } else {
-                vec!["".to_owned()]
+            let prefixes = match branch(prefix) {
+                Ok(v) => v,
+                Err(e) => return Box::new(std::iter::once(Err(e))),
             };
-            let postfixs: Vec<String> = if let Some(postfix) = postfix {
-                expand(postfix)?
-            } else {
-                vec!["".to_owned()]
+            let insides = match branch(inside) {
+                Ok(v) => v,
+                Err(e) => return Box::new(std::iter::once(Err(e))),
             };
-            for prefix in &prefixs {
-                for inside in &insides {
-                    for postfix in &postfixs {
-                        inner.push(format!("{}{}{}", prefix, inside, postfix));
-                    }
-                }
-            }
-            Ok(inner)
+            let postfixes = match branch(postfix) {
+                Ok(v) => v,
+                Err(e) => return Box::new(std::iter::once(Err(e))),
+            };
+            Box::new(CartesianProduct::new(prefixes, insides, postfixes))
         }
         parser::Node::Collection {
             items,
             start: _,
             end: _,
-        } => {
-            let mut inner = vec![];
-            for item in items {
-                let expansions = expand(item)?;
-                inner.extend(expansions);
-            }
-            Ok(inner)
-        }
+        } => Box::new(items.into_iter().flat_map(expand_node)),
         parser::Node::Range {
             from,
             to,
+            step,
             start: _,
             end: _,
-        } => {
-            // Get the numeric string length to be used later for zero padding
-            let zero_pad = if from.chars().nth(0) == Some('0') && from.len() > 1
-                || to.chars().nth(0) == Some('0')
-            {
-                if from.len() >= to.len() {
-                    from.len()
-                } else {
-                    to.len()
-                }
-            } else {
-                0
-            };
-            let from = if let Ok(from) = from.parse::<usize>() {
-                from
-            } else {
-                return Err(ExpansionError::NumConversionFailed(from.to_string()));
-            };
+        } => match expand_range(&from, &to, step.as_deref().map(|s| s.as_str())) {
+            Ok(values) => Box::new(values.into_iter().map(Ok)),
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        },
+    }
+}
 
-            let to = if let Ok(to) = to.parse::<usize>() {
-                to
-            } else {
-                return Err(ExpansionError::NumConversionFailed(to.to_string()));
-            };
-            let range = from..=to;
-            let mut inner = vec![];
-            for i in range {
-                inner.push(format!("{:0>width$}", i, width = zero_pad));
+/// Lazily yields the lexicographic cartesian product of `prefix × inside × postfix`,
+/// advancing the innermost (`postfix`) cursor first - matching the nested-loop ordering
+/// `expand` used before it was rewritten in terms of [`expand_iter`].
+struct CartesianProduct {
+    prefixes: Vec<String>,
+    insides: Vec<String>,
+    postfixes: Vec<String>,
+    pi: usize,
+    ii: usize,
+    qi: usize,
+}
+
+impl CartesianProduct {
+    fn new(prefixes: Vec<String>, insides: Vec<String>, postfixes: Vec<String>) -> Self {
+        Self {
+            prefixes,
+            insides,
+            postfixes,
+            pi: 0,
+            ii: 0,
+            qi: 0,
+        }
+    }
+}
+
+impl Iterator for CartesianProduct {
+    type Item = Result<String, ExpansionError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pi >= self.prefixes.len() || self.insides.is_empty() || self.postfixes.is_empty() {
+            return None;
+        }
+
+        let value = format!(
+            "{}{}{}",
+            self.prefixes[self.pi], self.insides[self.ii], self.postfixes[self.qi]
+        );
+
+        self.qi += 1;
+        if self.qi >= self.postfixes.len() {
+            self.qi = 0;
+            self.ii += 1;
+            if self.ii >= self.insides.len() {
+                self.ii = 0;
+                self.pi += 1;
             }
-            Ok(inner)
+        }
+
+        Some(Ok(value))
+    }
+}
+
+/// Expands a `Node::Range`'s `from`/`to`/`step` fields into its concrete values, shared by
+/// both [`expand`] (via [`expand_iter`]) and the `Range` arm of [`expand_node`].
+fn expand_range(from: &str, to: &str, step: Option<&str>) -> Result<Vec<String>, ExpansionError> {
+    let from_alpha = from.len() == 1 && from.chars().next().unwrap().is_ascii_alphabetic();
+    let to_alpha = to.len() == 1 && to.chars().next().unwrap().is_ascii_alphabetic();
+
+    if from_alpha || to_alpha {
+        if !(from_alpha && to_alpha) {
+            return Err(ExpansionError::MixedRangeBounds(
+                from.to_owned(),
+                to.to_owned(),
+            ));
+        }
+
+        let from = from.chars().next().unwrap() as u32;
+        let to = to.chars().next().unwrap() as u32;
+
+        let mut inner = vec![];
+        if from <= to {
+            for c in from..=to {
+                inner.push(char::from_u32(c).unwrap().to_string());
+            }
+        } else {
+            for c in (to..=from).rev() {
+                inner.push(char::from_u32(c).unwrap().to_string());
+            }
+        }
+        return Ok(inner);
+    }
+
+    // Get the digit string length to be used later for zero padding, ignoring a
+    // leading sign so the minus stays outside the padded digits (`{-05..05}`).
+    fn digits(s: &str) -> &str {
+        s.strip_prefix('-').unwrap_or(s)
+    }
+    let zero_pad = if digits(from).starts_with('0') && digits(from).len() > 1
+        || digits(to).starts_with('0')
+    {
+        std::cmp::max(digits(from).len(), digits(to).len())
+    } else {
+        0
+    };
+    let from_n = if let Ok(from) = from.parse::<i64>() {
+        from
+    } else {
+        return Err(ExpansionError::NumConversionFailed(from.to_owned()));
+    };
+
+    let to_n = if let Ok(to) = to.parse::<i64>() {
+        to
+    } else {
+        return Err(ExpansionError::NumConversionFailed(to.to_owned()));
+    };
+    let step = match step {
+        Some(step) => match step.parse::<i64>() {
+            Ok(0) | Err(_) => return Err(ExpansionError::InvalidStep(step.to_owned())),
+            Ok(step) => step.unsigned_abs(),
+        },
+        None => 1,
+    };
+
+    let pad = |n: i64| {
+        let sign = if n < 0 { "-" } else { "" };
+        format!("{sign}{:0>width$}", n.unsigned_abs(), width = zero_pad)
+    };
+
+    let mut inner = vec![];
+    if from_n <= to_n {
+        let mut i = from_n;
+        while i <= to_n {
+            inner.push(pad(i));
+            i += step as i64;
+        }
+    } else {
+        let mut i = from_n;
+        while i >= to_n {
+            inner.push(pad(i));
+            i -= step as i64;
         }
     }
+    Ok(inner)
 }
 
 /// Same functionality as [bracoxidize] but with explosive materials. This crates' all
@@ -217,6 +360,17 @@ pub fn explode(content: &str) -> Result<Vec<String>, Box<dyn std::error::Error>>
     Ok(expansions)
 }
 
+/// Lazy, streaming counterpart to [`explode`]: tokenizes and parses `content` eagerly (that
+/// part is cheap - it's proportional to the pattern's length, not its expansion), then
+/// returns an iterator that expands it on demand via [`expand_iter`].
+pub fn explode_iter(
+    content: &str,
+) -> Result<impl Iterator<Item = Result<String, ExpansionError>>, Box<dyn std::error::Error>> {
+    let tokens = tokenizer::tokenize(content)?;
+    let ast = parser::parse(&tokens)?;
+    Ok(expand_node(ast))
+}
+
 /// Errors that can occur during the Brace Expansion process.
 #[derive(Debug, PartialEq)]
 pub enum OxidizationError {
@@ -275,6 +429,27 @@ pub fn bracoxidize(content: &str) -> Result<Vec<String>, OxidizationError> {
     Ok(expanded)
 }
 
+/// Lazy, streaming counterpart to [`bracoxidize`]: tokenizes and parses `content` eagerly,
+/// then returns an iterator that expands it on demand via [`expand_iter`] instead of
+/// collecting every combination into a `Vec` upfront.
+pub fn bracoxidize_iter(
+    content: &str,
+) -> Result<impl Iterator<Item = Result<String, ExpansionError>>, OxidizationError> {
+    // Tokenize the input string
+    let tokens = match tokenizer::tokenize(content) {
+        Ok(tokens) => tokens,
+        Err(error) => return Err(OxidizationError::TokenizationError(error)),
+    };
+
+    // Parse the tokens into an abstract syntax tree
+    let ast = match parser::parse(&tokens) {
+        Ok(ast) => ast,
+        Err(error) => return Err(OxidizationError::ParsingError(error)),
+    };
+
+    Ok(expand_node(ast))
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -356,6 +531,7 @@ mod tests {
                         inside: Some(Box::new(Node::Range {
                             from: Arc::new("3".into()),
                             to: Arc::new("5".into()),
+                            step: None,
                             start: 21,
                             end: 26
                         })),
@@ -509,4 +685,136 @@ mod tests {
             Ok(vec!["1\\a".to_owned(), "1\\b".to_owned()])
         )
     }
+    #[test]
+    fn test_expand_range_alpha() {
+        assert_eq!(
+            bracoxidize("{a..e}"),
+            Ok(vec![
+                "a".to_owned(),
+                "b".to_owned(),
+                "c".to_owned(),
+                "d".to_owned(),
+                "e".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_alpha_descending() {
+        assert_eq!(
+            bracoxidize("{e..a}"),
+            Ok(vec![
+                "e".to_owned(),
+                "d".to_owned(),
+                "c".to_owned(),
+                "b".to_owned(),
+                "a".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_alpha_uppercase() {
+        assert_eq!(
+            bracoxidize("{A..D}"),
+            Ok(vec![
+                "A".to_owned(),
+                "B".to_owned(),
+                "C".to_owned(),
+                "D".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_with_step() {
+        assert_eq!(
+            bracoxidize("{0..10..2}"),
+            Ok(vec![
+                "0".to_owned(),
+                "2".to_owned(),
+                "4".to_owned(),
+                "6".to_owned(),
+                "8".to_owned(),
+                "10".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_with_zero_step_is_an_expansion_error() {
+        assert_eq!(
+            bracoxidize("{0..10..0}"),
+            Err(OxidizationError::ExpansionError(ExpansionError::InvalidStep(
+                "0".to_owned()
+            )))
+        )
+    }
+    #[test]
+    fn test_expand_range_signed() {
+        assert_eq!(
+            bracoxidize("{-2..2}"),
+            Ok(vec![
+                "-2".to_owned(),
+                "-1".to_owned(),
+                "0".to_owned(),
+                "1".to_owned(),
+                "2".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_descending() {
+        assert_eq!(
+            bracoxidize("{3..1}"),
+            Ok(vec!["3".to_owned(), "2".to_owned(), "1".to_owned()])
+        )
+    }
+    #[test]
+    fn test_expand_range_signed_zero_padded() {
+        assert_eq!(
+            bracoxidize("{-05..05}"),
+            Ok(vec![
+                "-05".to_owned(),
+                "-04".to_owned(),
+                "-03".to_owned(),
+                "-02".to_owned(),
+                "-01".to_owned(),
+                "00".to_owned(),
+                "01".to_owned(),
+                "02".to_owned(),
+                "03".to_owned(),
+                "04".to_owned(),
+                "05".to_owned(),
+            ])
+        )
+    }
+    #[test]
+    fn test_expand_range_mixed_bounds_is_an_expansion_error() {
+        assert_eq!(
+            bracoxidize("{a..5}"),
+            Err(OxidizationError::ExpansionError(
+                ExpansionError::MixedRangeBounds("a".to_owned(), "5".to_owned())
+            ))
+        )
+    }
+    #[test]
+    fn test_expand_iter_matches_expand() {
+        let ast = parser::parse(&tokenizer::tokenize("foo{1..3}bar{a,b}").unwrap()).unwrap();
+        let via_iter: Result<Vec<String>, ExpansionError> = expand_iter(&ast).collect();
+        assert_eq!(via_iter, expand(&ast));
+    }
+    #[test]
+    fn test_explode_iter_matches_explode() {
+        let via_iter: Vec<String> = explode_iter("{a,b}{1..2}")
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(via_iter, explode("{a,b}{1..2}").unwrap());
+    }
+    #[test]
+    fn test_bracoxidize_iter_matches_bracoxidize() {
+        let via_iter: Result<Vec<String>, ExpansionError> =
+            bracoxidize_iter("foo{1..3}bar").unwrap().collect();
+        assert_eq!(
+            via_iter.map_err(OxidizationError::ExpansionError),
+            bracoxidize("foo{1..3}bar")
+        );
+    }
 }
@@ -44,11 +44,12 @@ pub enum Node {
         end: usize,
     },
     /// Represents a range node.
-    /// It contains the starting and ending numbers of the range, along with the
-    /// starting position.
+    /// It contains the starting and ending numbers of the range, an optional step
+    /// (e.g. `{0..10..2}`), along with the starting position.
     Range {
         from: Arc<String>,
         to: Arc<String>,
+        step: Option<Arc<String>>,
         start: usize,
         end: usize,
     },
@@ -81,8 +82,10 @@ pub enum ParsingError {
     NothingInBraces(usize),
     /// Range can't have text in it.
     RangeCantHaveText(usize),
-    /// Extra Range Operator have used, e.g. `{3..5..}`
+    /// Extra Range Operator have used, e.g. `{3..5..7..}`
     ExtraRangeOperator(usize),
+    /// A second range operator was used but no step digits followed it, e.g. `{3..5..}`
+    StepLimitExpected(usize),
 }
 
 impl std::fmt::Display for ParsingError {
@@ -112,6 +115,9 @@ impl std::fmt::Display for ParsingError {
             ParsingError::ExtraRangeOperator(i) => {
                 write!(f, "Extra range operator (..) used at {i}")
             }
+            ParsingError::StepLimitExpected(i) => {
+                write!(f, "Range step not specified after second `..`. Expected at {i}")
+            }
         }
     }
 }
@@ -341,8 +347,9 @@ fn range(tokens: &Vec<Token>) -> Result<Node, ParsingError> {
     if tokens.is_empty() {
         return Err(ParsingError::NoTokens);
     }
-    let mut limits = (String::new(), String::new());
-    let mut is_start = true;
+    // 0: `from`, 1: `to`, 2: `step` (`{from..to..step}`)
+    let mut limits = (String::new(), String::new(), String::new());
+    let mut segment = 0_u8;
     let mut is_first = true;
     let mut count = 0_u8;
     let mut pos = (0_usize, 0_usize);
@@ -352,15 +359,15 @@ fn range(tokens: &Vec<Token>) -> Result<Node, ParsingError> {
             Token::OBra(s) => return Err(ParsingError::ExtraOBra(*s)),
             Token::CBra(s) => return Err(ParsingError::ExtraCBra(*s)),
             Token::Comma(s) => return Err(ParsingError::InvalidCommaUsage(*s)),
-            Token::Text(_, s) => return Err(ParsingError::RangeCantHaveText(*s)),
-            Token::Number(b, s) => {
+            Token::Text(b, s) | Token::Number(b, s) => {
                 if is_first {
                     pos.0 = *s;
                     is_first = false;
                 }
-                match is_start {
-                    true => limits.0.push_str(b),
-                    false => limits.1.push_str(b),
+                match segment {
+                    0 => limits.0.push_str(b),
+                    1 => limits.1.push_str(b),
+                    _ => limits.2.push_str(b),
                 }
             }
             Token::Range(e) => {
@@ -368,24 +375,34 @@ fn range(tokens: &Vec<Token>) -> Result<Node, ParsingError> {
                     return Err(ParsingError::RangeStartLimitExpected(*e));
                 }
                 count += 1;
-                if count != 1 {
+                if count > 2 {
                     return Err(ParsingError::ExtraRangeOperator(*e));
                 }
                 pos.1 = *e;
-                is_start = false;
+                segment += 1;
             }
         }
     }
     if limits.1.is_empty() {
         return Err(ParsingError::RangeEndLimitExpected(pos.1));
     }
-    let len = limits.1.len();
+    if segment == 2 && limits.2.is_empty() {
+        return Err(ParsingError::StepLimitExpected(pos.1));
+    }
+    let step = if limits.2.is_empty() {
+        None
+    } else {
+        Some(Arc::new(limits.2))
+    };
+    // The last filled segment (`to`, or `step` when present) is the one adjacent to `}`.
+    let tail_len = step.as_ref().map_or(limits.1.len(), |step| step.len());
     Ok(Node::Range {
         from: Arc::new(limits.0),
         to: Arc::new(limits.1),
+        step,
         start: pos.0 - 1,
         // +1 for '.', +1 for `}`
-        end: pos.1 + 2 + len,
+        end: pos.1 + 2 + tail_len,
     })
 }
 
@@ -785,6 +802,7 @@ mod tests {
                         inside: Some(Box::new(Node::Range {
                             from: Arc::new("3".into()),
                             to: Arc::new("5".into()),
+                            step: None,
                             start: 21,
                             end: 26
                         })),
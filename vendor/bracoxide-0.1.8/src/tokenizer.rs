@@ -233,6 +233,22 @@ pub fn tokenize(content: &str) -> Result<Vec<Token>, TokenizationError> {
                 tokenize_text_buffer(&mut tokens, &mut buffers, i);
                 buffers.1.push(c);
             }
+            ('-', _) if buffers.1.is_empty() => {
+                // A `-` immediately followed by a digit starts a signed number, used by
+                // range endpoints like `{-3..3}`. Fold it into the number buffer instead
+                // of the text buffer so it reaches the parser as a single token.
+                let mut r_iter = iter.clone();
+                match r_iter.next() {
+                    Some((_, cx)) if cx.is_ascii_digit() => {
+                        tokenize_text_buffer(&mut tokens, &mut buffers, i);
+                        buffers.1.push(c);
+                    }
+                    _ => {
+                        tokenize_number_buffer(&mut tokens, &mut buffers, i);
+                        buffers.0.push(c);
+                    }
+                }
+            }
             _ => {
                 tokenize_number_buffer(&mut tokens, &mut buffers, i);
                 buffers.0.push(c);